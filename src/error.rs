@@ -1,21 +1,30 @@
+use chrono::{NaiveDate, NaiveTime};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum ClockerError {
     #[error("Shift already complete for today.")]
     ShiftComplete,
-    #[error("Malformed lines in the input file:\n{}", format_errors(.0))]
-    FileParseError(Vec<csv::Error>),
+    #[error("No shift in progress for today.")]
+    NoActiveShift,
+    #[error("{0} is already set for today.")]
+    SlotAlreadyFilled(&'static str),
+    #[error("Cannot fill {0} yet; fill the prior slot first.")]
+    SlotNotReady(&'static str),
+    #[error("Duplicate entry for {0}.")]
+    DuplicateDate(NaiveDate),
+    #[error("Entry for {1} appears out of order after {0}.")]
+    DatesOutOfOrder(NaiveDate, NaiveDate),
+    #[error("Times for {0} are not monotonic (start_am <= end_am <= start_pm <= end_pm).")]
+    NonMonotonicTimes(NaiveDate),
+    #[error("{1} on {0} is in the future.")]
+    FutureTime(NaiveDate, NaiveTime),
+    #[error("{0} has {1} set without its matching start time.")]
+    EndWithoutStart(NaiveDate, &'static str),
+    #[error("Invalid range: start {0} is after end {1}.")]
+    InvalidRange(NaiveDate, NaiveDate),
     #[error("CSV error: {0}")]
     Csv(#[from] csv::Error),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
-
-fn format_errors(errors: &Vec<csv::Error>) -> String {
-    errors
-        .iter()
-        .map(|e| format!("{}", e))
-        .collect::<Vec<_>>()
-        .join("\n")
-}
@@ -5,11 +5,13 @@
 //! clocker [INPUT_FILE] [OUTPUT_FILE]
 //! ```
 
-use std::{env};
-use clap::Parser;
+use std::env;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
 
 use crate::timelog::TimeLog;
 
+mod error;
 mod timelog;
 
 /// Version of the app as defined in the Cargo.toml file
@@ -29,6 +31,63 @@ struct Cli {
     #[arg(default_value = DEFAULT_PATH)]
     input_file: String,
     output_file: Option<String>,
+    /// Refuse to write the log if it would violate an invariant (see `validate`)
+    #[arg(long)]
+    check: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Subcommands that operate on the log beyond the default clock-in/clock-out behavior
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Prints worked durations per day, with weekly and monthly subtotals
+    Report,
+    /// Writes the entries within an inclusive date range to a separate file
+    Export {
+        #[arg(long)]
+        start: NaiveDate,
+        #[arg(long)]
+        end: NaiveDate,
+        #[arg(long)]
+        output_path: String,
+    },
+    /// Prints a Markdown invoice for worked hours at a given hourly rate
+    Invoice {
+        #[arg(long)]
+        client: String,
+        #[arg(long)]
+        rate: f64,
+        #[arg(long, default_value = "USD")]
+        currency: String,
+        #[arg(long)]
+        start: Option<NaiveDate>,
+        #[arg(long)]
+        end: Option<NaiveDate>,
+        /// Round each morning/afternoon session up to the nearest N minutes
+        #[arg(long)]
+        round_minutes: Option<i64>,
+    },
+    /// Prints today's open slots and elapsed time without writing anything
+    Status,
+    /// Forces filling start_am (starts a new day, or fills it on today's entry)
+    In,
+    /// Forces filling the next open end slot (end_am or end_pm)
+    Out,
+    /// Drives the afternoon slots (start_pm and end_pm)
+    Break {
+        #[command(subcommand)]
+        action: BreakAction,
+    },
+}
+
+/// Which break slot to fill
+#[derive(Subcommand, Debug)]
+enum BreakAction {
+    /// Forces filling start_pm
+    Start,
+    /// Forces filling end_pm
+    End,
 }
 
 /// Entrypoint of the tool
@@ -39,5 +98,37 @@ fn main() {
     let output_filename = cli.output_file.as_deref().map(resolve_path).unwrap_or(input_filename.clone());
 
     let time_log = TimeLog::from_file(&input_filename).expect(&format!("Couldn't read {}", input_filename));
-    let _ = time_log.update().persist(&output_filename).expect("Couldn't write file");
+
+    match cli.command {
+        Some(Command::Report) => time_log.report(),
+        Some(Command::Export { start, end, output_path }) => {
+            let output_path = resolve_path(&output_path);
+            time_log
+                .export_range(start, end, &output_path)
+                .expect("Couldn't write export");
+        }
+        Some(Command::Invoice { client, rate, currency, start, end, round_minutes }) => {
+            println!("{}", time_log.invoice(&client, rate, &currency, start, end, round_minutes));
+        }
+        Some(Command::Status) => time_log.status(),
+        Some(Command::In) => {
+            let updated = time_log.clock_in().expect("Couldn't clock in");
+            updated.persist(&output_filename, cli.check).expect("Couldn't write file");
+        }
+        Some(Command::Out) => {
+            let updated = time_log.clock_out().expect("Couldn't clock out");
+            updated.persist(&output_filename, cli.check).expect("Couldn't write file");
+        }
+        Some(Command::Break { action: BreakAction::Start }) => {
+            let updated = time_log.break_start().expect("Couldn't start break");
+            updated.persist(&output_filename, cli.check).expect("Couldn't write file");
+        }
+        Some(Command::Break { action: BreakAction::End }) => {
+            let updated = time_log.break_end().expect("Couldn't end break");
+            updated.persist(&output_filename, cli.check).expect("Couldn't write file");
+        }
+        None => {
+            let _ = time_log.update().persist(&output_filename, cli.check).expect("Couldn't write file");
+        }
+    }
 }
@@ -5,13 +5,21 @@
 //!
 use chrono::{Local, NaiveDate, NaiveTime, Timelike};
 use std::{error::Error, fs, io};
+use ir::IrItem;
 use timelogentry::TimeLogEntry;
 
 mod timelogentry;
+mod report;
+mod export;
+mod invoice;
+mod explicit;
+mod validate;
+mod ir;
 
 /// Holds the different legal times of day to log
 #[derive(Debug)]
 enum TimeOfDay {
+    StartAM,
     EndAM,
     StartPM,
     EndPM,
@@ -28,44 +36,48 @@ enum UpdateAction {
 /// Main state structure. Holds information about the time and the existing log entries
 #[derive(Debug)]
 pub struct TimeLog {
-    entries: Vec<TimeLogEntry>,
+    items: Vec<IrItem>,
     today: NaiveDate,
     current_time: NaiveTime,
 }
 
 impl TimeLog {
-    /// Loads entries from the file at the given path.
+    /// Loads the file at the given path, preserving every line (comments,
+    /// header lines, unparsed rows) as an `IrItem`.
     pub fn from_file(filepath: &String) -> io::Result<Self> {
         if !fs::metadata(&filepath).is_ok() {
             eprintln!("Cannot find file {}", filepath);
             return Ok(Self::new(Vec::new()));
         }
 
-        let file = fs::File::open(filepath)?;
-        let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(file);
+        let contents = fs::read_to_string(filepath)?;
+        let items = contents.lines().map(ir::parse_line).collect();
 
-        let mut entries = Vec::new();
-        for log in reader.deserialize() {
-            match log {
-                Ok(log) => entries.push(log),
-                Err(e) => {
-                    eprintln!("Warning: Skipping malformed CSV record: {}", e);
-                    continue;
-                }
-            }
+        Ok(Self::from_items(items))
+    }
+
+    /// Writes the current items to the given filepath, echoing every
+    /// non-entry line back verbatim and re-serializing only the entries.
+    /// When `check` is set, refuses to write if `validate()` finds a
+    /// violated invariant.
+    pub fn persist(&self, filepath: &String, check: bool) -> Result<(), Box<dyn Error>> {
+        if check {
+            self.validate()?;
         }
 
-        Ok(Self::new(entries))
-    }
+        let needs_header = self.items.iter().any(|item| matches!(item, IrItem::Entry(_, _)))
+            && !self
+                .items
+                .iter()
+                .any(|item| matches!(item, IrItem::Raw(line) if line == ir::CSV_HEADER));
 
-    /// Writes the current entries to the given filepath.
-    pub fn persist(&self, filepath: &String) -> Result<(), Box<dyn Error>> {
-        let file = fs::File::create(filepath).unwrap();
-        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(file);
-        for entry in self.entries.iter() {
-            writer.serialize(entry)?;
+        let mut lines: Vec<String> = self.items.iter().map(ir::render_line).collect();
+        if needs_header {
+            lines.insert(0, ir::CSV_HEADER.to_string());
         }
-        writer.flush()?;
+        lines.push(String::new());
+
+        fs::write(filepath, lines.join("\n"))?;
         Ok(())
     }
 
@@ -75,8 +87,25 @@ impl TimeLog {
         self.apply_action(action)
     }
 
+    /// Returns the log's entries in file order, skipping comments, header
+    /// lines and unparsed rows.
+    fn entries(&self) -> Vec<&TimeLogEntry> {
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                IrItem::Entry(entry, _) => Some(entry),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Creates a new TimeLog from the given entries. Time and date are set to the current datetime
     fn new(entries: Vec<TimeLogEntry>) -> Self {
+        Self::from_items(entries.into_iter().map(|entry| IrItem::Entry(entry, Vec::new())).collect())
+    }
+
+    /// Creates a new TimeLog from already-parsed items. Time and date are set to the current datetime
+    fn from_items(items: Vec<IrItem>) -> Self {
         let now = Local::now();
         let today = now.date_naive();
         let current_time = now
@@ -87,7 +116,7 @@ impl TimeLog {
             .unwrap();
 
         Self {
-            entries,
+            items,
             today,
             current_time,
         }
@@ -95,7 +124,7 @@ impl TimeLog {
 
     /// Decides what action is appropriate based on the current state
     fn determine_action(&self) -> UpdateAction {
-        match self.entries.last() {
+        match self.entries().last().copied() {
             None => UpdateAction::NewDay(self.today, self.current_time),
             Some(TimeLogEntry { date: d, .. }) if d != &self.today => {
                 UpdateAction::NewDay(self.today, self.current_time)
@@ -116,18 +145,26 @@ impl TimeLog {
 
     /// Applies the given action and returns the result as a new TimeLog
     fn apply_action(&self, action: UpdateAction) -> Self {
-        let new_entries = match action {
-            UpdateAction::NoChange => self.entries.clone(),
-            UpdateAction::NewDay(date, time) => self
-                .entries
-                .iter()
-                .cloned()
-                .chain(std::iter::once(TimeLogEntry::new(date, time)))
-                .collect(),
+        let mut new_items = self.items.clone();
+
+        match action {
+            UpdateAction::NoChange => {}
+            UpdateAction::NewDay(date, time) => {
+                new_items.push(IrItem::Entry(TimeLogEntry::new(date, time), Vec::new()));
+            }
             UpdateAction::FillSlot(time_of_day, time) => {
-                let mut new_vec = self.entries.clone();
-                let new_entry = new_vec.last_mut().unwrap();
+                let new_entry = new_items
+                    .iter_mut()
+                    .rev()
+                    .find_map(|item| match item {
+                        IrItem::Entry(entry, _) => Some(entry),
+                        _ => None,
+                    })
+                    .expect("FillSlot requires an existing entry");
                 match time_of_day {
+                    TimeOfDay::StartAM => {
+                        new_entry.set_start_am(time);
+                    }
                     TimeOfDay::EndAM => {
                         new_entry.set_end_am(time);
                     }
@@ -138,12 +175,11 @@ impl TimeLog {
                         new_entry.set_end_pm(time);
                     }
                 }
-                new_vec
             }
         };
 
         Self {
-            entries: new_entries,
+            items: new_items,
             today: self.today,
             current_time: self.current_time,
         }
@@ -0,0 +1,97 @@
+//! Explicit `in`/`out`/`break`/`status` commands that give the user
+//! deterministic control over which slot gets written, instead of the
+//! auto-guessing `update()`/`determine_action()` path.
+use super::report::worked_duration;
+use super::{TimeLogEntry, TimeOfDay, TimeLog, UpdateAction};
+use crate::error::ClockerError;
+
+impl TimeLog {
+    /// Forces filling `start_am`: starts a new day if today has no entry
+    /// yet, or fills today's `start_am` if it is still empty.
+    pub fn clock_in(&self) -> Result<Self, ClockerError> {
+        match self.todays_entry() {
+            None => Ok(self.apply_action(UpdateAction::NewDay(self.today, self.current_time))),
+            Some(entry) if entry.start_am.is_none() => Ok(self.apply_action(
+                UpdateAction::FillSlot(TimeOfDay::StartAM, self.current_time),
+            )),
+            Some(_) => Err(ClockerError::SlotAlreadyFilled("start_am")),
+        }
+    }
+
+    /// Forces filling the next open "end" slot (`end_am` or `end_pm`).
+    pub fn clock_out(&self) -> Result<Self, ClockerError> {
+        let entry = self.todays_entry().ok_or(ClockerError::NoActiveShift)?;
+
+        if entry.start_am.is_none() {
+            Err(ClockerError::NoActiveShift)
+        } else if entry.end_am.is_none() {
+            Ok(self.apply_action(UpdateAction::FillSlot(TimeOfDay::EndAM, self.current_time)))
+        } else if entry.start_pm.is_none() {
+            Err(ClockerError::SlotNotReady("start_pm"))
+        } else if entry.end_pm.is_none() {
+            Ok(self.apply_action(UpdateAction::FillSlot(TimeOfDay::EndPM, self.current_time)))
+        } else {
+            Err(ClockerError::ShiftComplete)
+        }
+    }
+
+    /// Forces filling `start_pm`, marking the end of the lunch break and
+    /// the resumption of work in the afternoon.
+    pub fn break_start(&self) -> Result<Self, ClockerError> {
+        let entry = self.todays_entry().ok_or(ClockerError::NoActiveShift)?;
+
+        if entry.end_am.is_none() {
+            Err(ClockerError::SlotNotReady("end_am"))
+        } else if entry.start_pm.is_none() {
+            Ok(self.apply_action(UpdateAction::FillSlot(TimeOfDay::StartPM, self.current_time)))
+        } else {
+            Err(ClockerError::SlotAlreadyFilled("start_pm"))
+        }
+    }
+
+    /// Forces filling `end_pm`, marking the end of the work day.
+    pub fn break_end(&self) -> Result<Self, ClockerError> {
+        let entry = self.todays_entry().ok_or(ClockerError::NoActiveShift)?;
+
+        if entry.start_pm.is_none() {
+            Err(ClockerError::SlotNotReady("start_pm"))
+        } else if entry.end_pm.is_none() {
+            Ok(self.apply_action(UpdateAction::FillSlot(TimeOfDay::EndPM, self.current_time)))
+        } else {
+            Err(ClockerError::SlotAlreadyFilled("end_pm"))
+        }
+    }
+
+    /// Prints today's open slots and elapsed time without writing anything.
+    pub fn status(&self) {
+        match self.todays_entry() {
+            None => println!("No entry yet for today ({}).", self.today),
+            Some(entry) => {
+                println!("Today ({}):", self.today);
+                print_slot("start_am", entry.start_am);
+                print_slot("end_am", entry.end_am);
+                print_slot("start_pm", entry.start_pm);
+                print_slot("end_pm", entry.end_pm);
+
+                let worked = worked_duration(entry, self.today, self.current_time);
+                println!("Elapsed: {}h {:02}m", worked.num_minutes() / 60, worked.num_minutes() % 60);
+            }
+        }
+    }
+
+    /// Returns today's entry, if the log has one
+    fn todays_entry(&self) -> Option<&TimeLogEntry> {
+        self.entries()
+            .last()
+            .copied()
+            .filter(|entry| entry.date == self.today)
+    }
+}
+
+/// Prints a single slot's state: its time if filled, or "open"
+fn print_slot(name: &str, time: Option<chrono::NaiveTime>) {
+    match time {
+        Some(time) => println!("  {}: {}", name, time),
+        None => println!("  {}: open", name),
+    }
+}
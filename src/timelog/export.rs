@@ -0,0 +1,32 @@
+//! Slicing the log down to an inclusive date range for export
+use chrono::NaiveDate;
+use std::error::Error;
+
+use super::TimeLog;
+use crate::error::ClockerError;
+
+impl TimeLog {
+    /// Writes only the entries whose date falls within `[start, end]` to
+    /// `filepath`, leaving the entries held by `self` untouched.
+    ///
+    /// The bounds are found with a binary search instead of a full scan,
+    /// which relies on `entries` being sorted ascending by date (as
+    /// `validate()` checks); on an unsorted log the subset is not defined.
+    pub fn export_range(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        filepath: &String,
+    ) -> Result<(), Box<dyn Error>> {
+        if start > end {
+            return Err(Box::new(ClockerError::InvalidRange(start, end)));
+        }
+
+        let entries = self.entries();
+        let lower = entries.partition_point(|entry| entry.date < start);
+        let upper = entries.partition_point(|entry| entry.date <= end);
+
+        let subset = entries[lower..upper].iter().map(|entry| (*entry).clone()).collect();
+        Self::new(subset).persist(filepath, false)
+    }
+}
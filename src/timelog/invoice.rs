@@ -0,0 +1,112 @@
+//! Turning logged hours into a billable document
+use chrono::{Duration, NaiveDate};
+
+use super::report::worked_sessions;
+use super::TimeLog;
+
+impl TimeLog {
+    /// Renders a Markdown invoice covering `[start, end]` (an unset bound
+    /// leaves that side of the range open), billing worked hours at `rate`
+    /// per hour.
+    /// `round_minutes`, when given, rounds each morning/afternoon session up
+    /// to the nearest multiple of that many minutes before billing it.
+    pub fn invoice(
+        &self,
+        client: &str,
+        rate: f64,
+        currency: &str,
+        start: Option<NaiveDate>,
+        end: Option<NaiveDate>,
+        round_minutes: Option<i64>,
+    ) -> String {
+        let mut lines = Vec::new();
+        let mut total_hours = 0.0;
+
+        lines.push(format!("# Invoice for {}", client));
+        if let Some(period) = format_period(start, end) {
+            lines.push(period);
+        }
+        lines.push(String::new());
+        lines.push(format!("| Date | Hours | Amount ({}) |", currency));
+        lines.push("|---|---|---|".to_string());
+
+        for entry in self.entries() {
+            if start.is_some_and(|start| entry.date < start) {
+                continue;
+            }
+            if end.is_some_and(|end| entry.date > end) {
+                continue;
+            }
+
+            let (am, pm) = worked_sessions(entry, self.today, self.current_time);
+            let worked = round_up(am, round_minutes) + round_up(pm, round_minutes);
+            let hours = worked.num_minutes() as f64 / 60.0;
+            let amount = hours * rate;
+            total_hours += hours;
+
+            lines.push(format!("| {} | {:.2} | {:.2} |", entry.date, hours, amount));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "**Total: {:.2} hours, {:.2} {}**",
+            total_hours,
+            total_hours * rate,
+            currency
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/// Describes the requested period, if either bound was given
+fn format_period(start: Option<NaiveDate>, end: Option<NaiveDate>) -> Option<String> {
+    match (start, end) {
+        (None, None) => None,
+        (start, end) => Some(format!(
+            "Period: {} to {}",
+            start.map(|d| d.to_string()).unwrap_or_else(|| "the beginning of the log".to_string()),
+            end.map(|d| d.to_string()).unwrap_or_else(|| "today".to_string()),
+        )),
+    }
+}
+
+/// Rounds a session's duration up to the nearest `granularity_minutes`, if
+/// given. A zero-length session (the slot wasn't worked) is left at zero
+/// rather than rounded up to a full billable unit.
+fn round_up(duration: Duration, granularity_minutes: Option<i64>) -> Duration {
+    if duration.is_zero() {
+        return duration;
+    }
+
+    match granularity_minutes {
+        Some(granularity) if granularity > 0 => {
+            let minutes = duration.num_minutes();
+            let rounded = ((minutes + granularity - 1) / granularity) * granularity;
+            Duration::minutes(rounded)
+        }
+        _ => duration,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_up_rounds_a_session_to_the_next_granularity() {
+        assert_eq!(round_up(Duration::minutes(7), Some(15)), Duration::minutes(15));
+        assert_eq!(round_up(Duration::minutes(15), Some(15)), Duration::minutes(15));
+        assert_eq!(round_up(Duration::minutes(16), Some(15)), Duration::minutes(30));
+    }
+
+    #[test]
+    fn round_up_leaves_an_unworked_session_at_zero() {
+        assert_eq!(round_up(Duration::zero(), Some(15)), Duration::zero());
+    }
+
+    #[test]
+    fn round_up_without_a_granularity_is_a_no_op() {
+        assert_eq!(round_up(Duration::minutes(7), None), Duration::minutes(7));
+    }
+}
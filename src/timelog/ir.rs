@@ -0,0 +1,181 @@
+//! Intermediate representation layered over the raw CSV lines.
+//!
+//! `persist()` used to re-serialize only the known struct fields, which
+//! meant any comment, custom header or extra column a user added was
+//! destroyed on every write. Parsing into `IrItem`s keeps every non-entry
+//! line around verbatim, so it can be echoed back on the next write instead
+//! of being re-inferred (or dropped). Entry lines are always re-serialized
+//! from their parsed fields, whether or not they changed, so their original
+//! formatting is not kept; only their extra columns are carried over.
+use super::TimeLogEntry;
+
+/// The canonical CSV header row clocker writes for its own entries
+pub(super) const CSV_HEADER: &str = "date,start_am,end_am,start_pm,end_pm";
+
+/// A single line of the log file, preserved in file order
+#[derive(Debug, Clone)]
+pub(super) enum IrItem {
+    /// A `#`-prefixed comment line; holds the text after the `#`
+    Comment(String),
+    /// An `@key=value` header line, e.g. `@owner=Jane Doe`
+    Header(String, String),
+    /// A successfully parsed log entry, plus any trailing columns beyond
+    /// the known fields (preserved verbatim so a user's custom columns
+    /// survive a write)
+    Entry(TimeLogEntry, Vec<String>),
+    /// Anything else: blank lines, the CSV header row, or lines that don't
+    /// parse as an entry at all
+    Raw(String),
+}
+
+/// Parses a single line of the log file into an `IrItem`
+pub(super) fn parse_line(line: &str) -> IrItem {
+    if line.trim().is_empty() || line == CSV_HEADER {
+        return IrItem::Raw(line.to_string());
+    }
+    if let Some(comment) = line.strip_prefix('#') {
+        return IrItem::Comment(comment.to_string());
+    }
+    if let Some(header) = line.strip_prefix('@') {
+        if let Some((key, value)) = header.split_once('=') {
+            return IrItem::Header(key.to_string(), value.to_string());
+        }
+        return IrItem::Raw(line.to_string());
+    }
+
+    match parse_entry(line) {
+        Some((entry, extra)) => IrItem::Entry(entry, extra),
+        None => IrItem::Raw(line.to_string()),
+    }
+}
+
+/// Renders an `IrItem` back into its line of text
+pub(super) fn render_line(item: &IrItem) -> String {
+    match item {
+        IrItem::Comment(content) => format!("#{}", content),
+        IrItem::Header(key, value) => format!("@{}={}", key, value),
+        IrItem::Raw(line) => line.clone(),
+        IrItem::Entry(entry, extra) => {
+            let mut line = serialize_entry(entry);
+            for field in extra {
+                line.push(',');
+                if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+                    line.push('"');
+                    line.push_str(&field.replace('"', "\"\""));
+                    line.push('"');
+                } else {
+                    line.push_str(field);
+                }
+            }
+            line
+        }
+    }
+}
+
+/// Number of fields `TimeLogEntry` itself accounts for
+const KNOWN_FIELDS: usize = 5;
+
+/// Parses a single CSV row into a `TimeLogEntry`, plus any fields beyond
+/// the known ones (kept verbatim rather than dropped), if it fits that
+/// shape. Rows shorter than `KNOWN_FIELDS` (e.g. a fresh clock-in with only
+/// `date,start_am`) are padded with empty fields so `TimeLogEntry`'s
+/// `#[serde(default)]` slots still deserialize, matching how the baseline's
+/// `reader.deserialize()` tolerated missing trailing columns.
+fn parse_entry(line: &str) -> Option<(TimeLogEntry, Vec<String>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(line.as_bytes());
+    let record = reader.records().next()?.ok()?;
+
+    let known: csv::StringRecord = record
+        .iter()
+        .chain(std::iter::repeat(""))
+        .take(KNOWN_FIELDS)
+        .collect();
+    let entry: TimeLogEntry = known.deserialize(None).ok()?;
+    let extra = record.iter().skip(KNOWN_FIELDS).map(String::from).collect();
+
+    Some((entry, extra))
+}
+
+/// Serializes a single `TimeLogEntry` back into its CSV row
+fn serialize_entry(entry: &TimeLogEntry) -> String {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    writer.serialize(entry).expect("TimeLogEntry always serializes");
+    let bytes = writer.into_inner().expect("writing to a Vec never fails");
+    String::from_utf8(bytes)
+        .expect("csv output is valid utf8")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comment_lines_round_trip() {
+        let item = parse_line("# owed to Jane");
+        assert!(matches!(item, IrItem::Comment(ref text) if text == " owed to Jane"));
+        assert_eq!(render_line(&item), "# owed to Jane");
+    }
+
+    #[test]
+    fn header_lines_round_trip() {
+        let item = parse_line("@owner=Jane Doe");
+        assert!(matches!(item, IrItem::Header(ref key, ref value) if key == "owner" && value == "Jane Doe"));
+        assert_eq!(render_line(&item), "@owner=Jane Doe");
+    }
+
+    #[test]
+    fn blank_and_header_row_lines_are_kept_raw() {
+        assert!(matches!(parse_line(""), IrItem::Raw(ref line) if line.is_empty()));
+        assert!(matches!(parse_line(CSV_HEADER), IrItem::Raw(ref line) if line == CSV_HEADER));
+    }
+
+    #[test]
+    fn entry_lines_round_trip_with_extra_columns_preserved() {
+        let line = "2026-07-20,09:00:00,12:00:00,13:00:00,17:00:00,billable,project-x";
+        let item = parse_line(line);
+        match &item {
+            IrItem::Entry(entry, extra) => {
+                assert_eq!(entry.date.to_string(), "2026-07-20");
+                assert_eq!(extra, &vec!["billable".to_string(), "project-x".to_string()]);
+            }
+            _ => panic!("expected an Entry, got {:?}", item),
+        }
+        assert_eq!(render_line(&item), line);
+    }
+
+    #[test]
+    fn unparseable_lines_are_kept_raw() {
+        assert!(matches!(parse_line("not,enough,fields"), IrItem::Raw(_)));
+    }
+
+    #[test]
+    fn a_start_am_only_entry_parses_and_round_trips() {
+        let line = "2026-07-20,09:00:00";
+        let item = parse_line(line);
+        match &item {
+            IrItem::Entry(entry, extra) => {
+                assert_eq!(entry.date.to_string(), "2026-07-20");
+                assert_eq!(entry.start_am.unwrap().to_string(), "09:00:00");
+                assert_eq!(entry.end_am, None);
+                assert!(extra.is_empty());
+            }
+            _ => panic!("expected an Entry, got {:?}", item),
+        }
+        assert_eq!(render_line(&item), line);
+    }
+
+    #[test]
+    fn header_lines_preserve_their_original_spacing() {
+        let line = "@owner = Jane Doe";
+        let item = parse_line(line);
+        assert!(matches!(item, IrItem::Header(ref key, ref value) if key == "owner " && value == " Jane Doe"));
+        assert_eq!(render_line(&item), line);
+    }
+}
@@ -0,0 +1,182 @@
+//! Duration accounting over logged entries, aggregated by day/week/month
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime};
+use std::collections::BTreeMap;
+
+use super::{TimeLog, TimeLogEntry};
+
+impl TimeLog {
+    /// Prints a table of worked durations per day, with rolling week and month
+    /// subtotals and a grand total.
+    pub fn report(&self) {
+        let daily = self.daily_durations();
+
+        println!("{:<12} {:>10}", "Date", "Worked");
+        println!("{}", "-".repeat(23));
+
+        let mut week_key = None;
+        let mut week_total = Duration::zero();
+        let mut month_key = None;
+        let mut month_total = Duration::zero();
+        let mut grand_total = Duration::zero();
+
+        for (date, worked) in &daily {
+            let this_week = week_start(*date);
+            let this_month = (date.year(), date.month());
+
+            if week_key.is_some() && week_key != Some(this_week) {
+                println!("{:<12} {:>10}", "  week", format_duration(week_total));
+                week_total = Duration::zero();
+            }
+            if month_key.is_some() && month_key != Some(this_month) {
+                println!("{:<12} {:>10}", "  month", format_duration(month_total));
+                month_total = Duration::zero();
+            }
+
+            println!("{:<12} {:>10}", date.to_string(), format_duration(*worked));
+
+            week_key = Some(this_week);
+            week_total += *worked;
+            month_key = Some(this_month);
+            month_total += *worked;
+            grand_total += *worked;
+        }
+
+        if week_key.is_some() {
+            println!("{:<12} {:>10}", "  week", format_duration(week_total));
+        }
+        if month_key.is_some() {
+            println!("{:<12} {:>10}", "  month", format_duration(month_total));
+        }
+        println!("{}", "-".repeat(23));
+        println!("{:<12} {:>10}", "total", format_duration(grand_total));
+    }
+
+    /// Computes the worked duration for each day present in `entries`, keyed
+    /// by date and kept in ascending order. Entries sharing a day (which
+    /// `validate()` rejects, but `persist()` only checks that when asked)
+    /// are summed rather than letting the later one win.
+    fn daily_durations(&self) -> BTreeMap<NaiveDate, Duration> {
+        let mut totals = BTreeMap::new();
+        for entry in self.entries() {
+            let worked = worked_duration(entry, self.today, self.current_time);
+            *totals.entry(entry.date).or_insert_with(Duration::zero) += worked;
+        }
+        totals
+    }
+}
+
+/// Computes how much time was worked for a single entry, treating an open
+/// slot on today's entry as ongoing (clamped to `current_time`) and any
+/// other missing slot as zero.
+pub(super) fn worked_duration(
+    entry: &TimeLogEntry,
+    today: NaiveDate,
+    current_time: NaiveTime,
+) -> Duration {
+    let (am, pm) = worked_sessions(entry, today, current_time);
+    am + pm
+}
+
+/// Computes the morning and afternoon session durations separately, so
+/// callers that bill per session (e.g. invoicing) can round each one
+/// independently instead of rounding their sum.
+pub(super) fn worked_sessions(
+    entry: &TimeLogEntry,
+    today: NaiveDate,
+    current_time: NaiveTime,
+) -> (Duration, Duration) {
+    (
+        slot_duration(entry.start_am, entry.end_am, entry.date, today, current_time),
+        slot_duration(entry.start_pm, entry.end_pm, entry.date, today, current_time),
+    )
+}
+
+/// Computes the duration of a single half-day slot
+fn slot_duration(
+    start: Option<NaiveTime>,
+    end: Option<NaiveTime>,
+    date: NaiveDate,
+    today: NaiveDate,
+    current_time: NaiveTime,
+) -> Duration {
+    match (start, end) {
+        (Some(start), Some(end)) => end - start,
+        (Some(start), None) if date == today => current_time - start,
+        (Some(_), None) => {
+            eprintln!("Warning: {} has an open slot that was never closed", date);
+            Duration::zero()
+        }
+        (None, _) => Duration::zero(),
+    }
+}
+
+/// Returns the Monday that starts the ISO week containing `date`
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Formats a `Duration` as `Hh MMm` (hours unpadded, minutes two digits)
+fn format_duration(duration: Duration) -> String {
+    let total_minutes = duration.num_minutes();
+    format!("{}h {:02}m", total_minutes / 60, total_minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn worked_duration_sums_closed_am_and_pm_sessions() {
+        let entry = TimeLogEntry {
+            date: date(2026, 7, 20),
+            start_am: Some(time(9, 0)),
+            end_am: Some(time(12, 0)),
+            start_pm: Some(time(13, 0)),
+            end_pm: Some(time(17, 30)),
+        };
+        let worked = worked_duration(&entry, date(2026, 7, 25), time(0, 0));
+        assert_eq!(worked, Duration::hours(7) + Duration::minutes(30));
+    }
+
+    #[test]
+    fn worked_duration_clamps_todays_open_slot_to_current_time() {
+        let today = date(2026, 7, 25);
+        let entry = TimeLogEntry {
+            date: today,
+            start_am: Some(time(9, 0)),
+            end_am: None,
+            start_pm: None,
+            end_pm: None,
+        };
+        let worked = worked_duration(&entry, today, time(10, 30));
+        assert_eq!(worked, Duration::hours(1) + Duration::minutes(30));
+    }
+
+    #[test]
+    fn worked_duration_treats_a_past_days_open_slot_as_zero() {
+        let entry = TimeLogEntry {
+            date: date(2026, 7, 20),
+            start_am: Some(time(9, 0)),
+            end_am: None,
+            start_pm: None,
+            end_pm: None,
+        };
+        let worked = worked_duration(&entry, date(2026, 7, 25), time(10, 30));
+        assert_eq!(worked, Duration::zero());
+    }
+
+    #[test]
+    fn week_start_returns_the_preceding_monday() {
+        assert_eq!(week_start(date(2026, 7, 23)), date(2026, 7, 20));
+        assert_eq!(week_start(date(2026, 7, 20)), date(2026, 7, 20));
+    }
+}
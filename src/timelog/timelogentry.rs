@@ -28,6 +28,11 @@ impl TimeLogEntry {
         }
     }
 
+    /// Sets time for the start of the morning
+    pub fn set_start_am(&mut self, start_am: NaiveTime) {
+        self.start_am = Some(start_am);
+    }
+
     /// Sets time for the end of morning
     pub fn set_end_am(&mut self, end_am: NaiveTime) {
         self.end_am = Some(end_am);
@@ -0,0 +1,117 @@
+//! Invariant checks that guard against writing out a corrupt log
+use super::TimeLog;
+use crate::error::ClockerError;
+
+impl TimeLog {
+    /// Checks `entries` against the invariants the rest of the app relies
+    /// on, returning the first violation found:
+    /// - dates are strictly increasing, with no duplicate days
+    /// - within a day, present times are monotonic: `start_am <= end_am <=
+    ///   start_pm <= end_pm`
+    /// - no time is in the future relative to `current_time`
+    /// - no "end" slot is set while its matching "start" slot is missing
+    pub fn validate(&self) -> Result<(), ClockerError> {
+        let entries = self.entries();
+
+        for window in entries.windows(2) {
+            let (previous, entry) = (window[0], window[1]);
+            if entry.date == previous.date {
+                return Err(ClockerError::DuplicateDate(entry.date));
+            }
+            if entry.date < previous.date {
+                return Err(ClockerError::DatesOutOfOrder(previous.date, entry.date));
+            }
+        }
+
+        for entry in entries {
+            if entry.end_am.is_some() && entry.start_am.is_none() {
+                return Err(ClockerError::EndWithoutStart(entry.date, "end_am"));
+            }
+            if entry.end_pm.is_some() && entry.start_pm.is_none() {
+                return Err(ClockerError::EndWithoutStart(entry.date, "end_pm"));
+            }
+
+            let times = [entry.start_am, entry.end_am, entry.start_pm, entry.end_pm]
+                .into_iter()
+                .flatten();
+            let mut previous_time = None;
+            for time in times {
+                if let Some(previous_time) = previous_time {
+                    if time < previous_time {
+                        return Err(ClockerError::NonMonotonicTimes(entry.date));
+                    }
+                }
+                previous_time = Some(time);
+            }
+
+            if entry.date > self.today {
+                return Err(ClockerError::FutureTime(
+                    entry.date,
+                    entry.start_am.unwrap_or(self.current_time),
+                ));
+            }
+            if entry.date == self.today {
+                for time in [entry.start_am, entry.end_am, entry.start_pm, entry.end_pm]
+                    .into_iter()
+                    .flatten()
+                {
+                    if time > self.current_time {
+                        return Err(ClockerError::FutureTime(entry.date, time));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ir::IrItem;
+    use super::super::TimeLogEntry;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn log(entries: Vec<TimeLogEntry>, today: NaiveDate, current_time: NaiveTime) -> TimeLog {
+        TimeLog {
+            items: entries.into_iter().map(|entry| IrItem::Entry(entry, Vec::new())).collect(),
+            today,
+            current_time,
+        }
+    }
+
+    #[test]
+    fn validate_accepts_a_well_formed_log() {
+        let entries = vec![
+            TimeLogEntry::new(date(2026, 7, 20), time(9, 0)),
+            TimeLogEntry::new(date(2026, 7, 21), time(9, 0)),
+        ];
+        assert!(log(entries, date(2026, 7, 25), time(12, 0)).validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_a_duplicate_date() {
+        let entries = vec![
+            TimeLogEntry::new(date(2026, 7, 20), time(9, 0)),
+            TimeLogEntry::new(date(2026, 7, 20), time(10, 0)),
+        ];
+        let result = log(entries, date(2026, 7, 25), time(12, 0)).validate();
+        assert!(matches!(result, Err(ClockerError::DuplicateDate(_))));
+    }
+
+    #[test]
+    fn validate_rejects_a_future_time() {
+        let entries = vec![TimeLogEntry::new(date(2026, 7, 25), time(23, 0))];
+        let result = log(entries, date(2026, 7, 25), time(12, 0)).validate();
+        assert!(matches!(result, Err(ClockerError::FutureTime(_, _))));
+    }
+}